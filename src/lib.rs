@@ -9,7 +9,7 @@
 //!
 //! let mut cache = FifoCache::new(100, Duration::from_secs(60));
 //! cache.insert("key1", "value1");
-//! 
+//!
 //! if let Some(value) = cache.get(&"key1") {
 //!   println!("Found: {}", value);
 //! }
@@ -21,29 +21,112 @@ use std::collections::{hash_map, HashMap, VecDeque};
 #[cfg(feature = "ttl")]
 use std::time::{Duration, Instant};
 
+/// Eviction policy used by a [`FifoCache`], selected at construction via
+/// [`FifoCache::with_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+  /// Plain FIFO: the oldest entry is evicted first, regardless of how often it was accessed.
+  Fifo,
+  /// S3-FIFO: entries start in a small probationary queue and are promoted to a larger main
+  /// queue once they've been requested at least twice while there, so hot entries survive longer than strict insertion
+  /// order would allow. See [`FifoCache::with_policy`] for details.
+  S3Fifo,
+}
+
 /// A cache entry that stores a value along with its expiration time.
 #[derive(Debug, Clone)]
 struct CacheEntry<V> {
   value: V,
+  // The hard expiry: `get` (and, via `cleanup_expired`, the whole cache) never returns an
+  // entry past this point. When `soft_expires_at` is also set, `get` stops at whichever of the
+  // two comes first, while `get_stale` keeps serving the entry until `expires_at`.
   #[cfg(feature = "ttl")]
   expires_at: Instant,
+  // Set when the cache has a soft TTL configured (see `FifoCache::set_soft_ttl`). Once this
+  // passes, plain `get` treats the entry as gone, but `get_stale` will keep serving it (and
+  // reporting it as stale) until `expires_at`.
+  #[cfg(feature = "ttl")]
+  soft_expires_at: Option<Instant>,
+  // Saturating access-frequency counter in 0..=3, used by the S3-FIFO policy to decide
+  // whether an entry gets to stick around on eviction. Unused (and never incremented) under
+  // `Policy::Fifo`.
+  freq: u8,
+}
+
+// Computes the "weight" of a key-value pair for weight-based capacity bounding.
+type Weigher<K, V> = Box<dyn Fn(&K, &V) -> usize>;
+
+/// Why an entry was evicted from a [`FifoCache`], passed to the closure registered via
+/// [`FifoCache::set_on_evict`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictReason {
+  /// The entry was evicted to keep the cache within `max_size` and/or `max_weight`.
+  Capacity,
+  /// The entry was removed by [`FifoCache::cleanup_expired`] after its TTL passed.
+  Expired,
+  /// The entry was removed explicitly via [`FifoCache::remove`].
+  Removed,
 }
 
+// Called when an entry leaves the cache, with the reason it left.
+type OnEvict<K, V> = Box<dyn FnMut(K, V, EvictReason)>;
+
 /// A FIFO cache with TTL support.
 ///
 /// This cache maintains insertion order and evicts the oldest entries when
 /// the maximum size is reached. Entries also expire after the specified TTL.
-/// 
+///
 /// Note that:
 /// - reinserting an existing entry will not move it back to the front
 /// - the maximum capacity may *very briefly* be exceeded by 1
-#[derive(Debug)]
 pub struct FifoCache<K, V> {
   map: HashMap<K, CacheEntry<V>>,
+  // Plain FIFO order. Only used under `Policy::Fifo`.
   order: VecDeque<K>,
+  // S3-FIFO queues. Only used under `Policy::S3Fifo`.
+  // `small` and `main` hold keys that are currently in `map`; `ghost` holds only keys of
+  // recently evicted entries (no values), so it can remember who got kicked out.
+  small: VecDeque<K>,
+  main: VecDeque<K>,
+  ghost: VecDeque<K>,
   max_size: usize,
+  policy: Policy,
   #[cfg(feature = "ttl")]
   default_ttl: Duration,
+  // Optional soft TTL, set via `set_soft_ttl`, enabling stale-while-revalidate reads through
+  // `get_stale`. When `None`, entries have no soft expiry and behave as before: `get` and
+  // `get_stale` both stop returning them at `default_ttl`/the per-entry TTL.
+  #[cfg(feature = "ttl")]
+  soft_ttl: Option<Duration>,
+  // Optional weight-based capacity bounding, set via `set_weigher`. When `None`, only
+  // `max_size` (entry count) bounds the cache.
+  weigher: Option<Weigher<K, V>>,
+  max_weight: Option<usize>,
+  current_weight: usize,
+  // Fired whenever an entry is pruned, expired or removed (not on `clear`), set via `set_on_evict`.
+  on_evict: Option<OnEvict<K, V>>,
+}
+
+impl<K: std::fmt::Debug, V: std::fmt::Debug> std::fmt::Debug for FifoCache<K, V> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let mut s = f.debug_struct("FifoCache");
+    s.field("map", &self.map)
+      .field("order", &self.order)
+      .field("small", &self.small)
+      .field("main", &self.main)
+      .field("ghost", &self.ghost)
+      .field("max_size", &self.max_size)
+      .field("policy", &self.policy);
+    #[cfg(feature = "ttl")]
+    s.field("default_ttl", &self.default_ttl);
+    #[cfg(feature = "ttl")]
+    s.field("soft_ttl", &self.soft_ttl);
+    s.field("weigher", &self.weigher.as_ref().map(|_| "<fn>"))
+      .field("max_weight", &self.max_weight)
+      .field("current_weight", &self.current_weight)
+      .field("on_evict", &self.on_evict.as_ref().map(|_| "<fn>"))
+      .finish()
+  }
 }
 
 impl<K, V> FifoCache<K, V>
@@ -53,6 +136,9 @@ where
 {
   /// Creates a new FIFO cache with the specified maximum size and default TTL.
   ///
+  /// This uses the plain [`Policy::Fifo`] eviction policy; see [`Self::with_policy`] to pick
+  /// a different one.
+  ///
   /// # Arguments
   ///
   /// * `max_size` - Maximum number of entries the cache can hold
@@ -61,25 +147,105 @@ where
     max_size: usize,
     #[cfg(feature = "ttl")]
     default_ttl: Duration
+  ) -> Self {
+    Self::with_policy(
+      max_size,
+      #[cfg(feature = "ttl")]
+      default_ttl,
+      Policy::Fifo,
+    )
+  }
+
+  /// Creates a new cache with the specified maximum size, default TTL and eviction policy.
+  ///
+  /// # Arguments
+  ///
+  /// * `max_size` - Maximum number of entries the cache can hold
+  /// * `default_ttl` - Default time-to-live for cache entries
+  /// * `policy` - The eviction policy to use, see [`Policy`]
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # #[cfg(feature = "ttl")] {
+  /// use fifo_cache::{FifoCache, Policy};
+  /// use std::time::Duration;
+  ///
+  /// let mut cache = FifoCache::with_policy(100, Duration::from_secs(60), Policy::S3Fifo);
+  /// cache.insert("key1", "value1");
+  /// # }
+  /// ```
+  pub fn with_policy(
+    max_size: usize,
+    #[cfg(feature = "ttl")]
+    default_ttl: Duration,
+    policy: Policy,
   ) -> Self {
     Self {
       map: HashMap::with_capacity(max_size + 1),
       order: VecDeque::with_capacity(max_size + 1),
+      small: VecDeque::new(),
+      main: VecDeque::new(),
+      ghost: VecDeque::new(),
       max_size,
+      policy,
       #[cfg(feature = "ttl")]
       default_ttl,
+      #[cfg(feature = "ttl")]
+      soft_ttl: None,
+      weigher: None,
+      max_weight: None,
+      current_weight: 0,
+      on_evict: None,
     }
   }
 
+  /// Creates a new cache with weight-based capacity bounding (see
+  /// [`set_weigher`](Self::set_weigher)) enabled from construction, instead of having to call
+  /// `set_weigher` separately right after.
+  ///
+  /// # Arguments
+  ///
+  /// * `max_size` - Maximum number of entries the cache can hold
+  /// * `default_ttl` - Default time-to-live for cache entries
+  /// * `policy` - The eviction policy to use, see [`Policy`]
+  /// * `weigher` - Computes the weight of a key-value pair
+  /// * `max_weight` - The maximum total weight the cache may hold
+  pub fn with_weigher<F>(
+    max_size: usize,
+    #[cfg(feature = "ttl")]
+    default_ttl: Duration,
+    policy: Policy,
+    weigher: F,
+    max_weight: usize,
+  ) -> Self
+  where
+    F: Fn(&K, &V) -> usize + 'static,
+  {
+    let mut cache = Self::with_policy(
+      max_size,
+      #[cfg(feature = "ttl")]
+      default_ttl,
+      policy,
+    );
+    cache.set_weigher(weigher, max_weight);
+    cache
+  }
+
   /// Retrieves a value from the cache if it exists and hasn't expired.
   ///
+  /// Under the [`Policy::S3Fifo`] policy this also bumps the entry's access-frequency
+  /// counter, which is why this takes `&mut self` rather than `&self`.
+  ///
   /// # Arguments
   ///
   /// * `key` - The key to look up
   ///
   /// # Returns
   ///
-  /// `Some(&V)` if the key exists and hasn't expired, `None` otherwise.
+  /// `Some(&V)` if the key exists and hasn't expired, `None` otherwise. If a soft TTL is
+  /// configured (see [`set_soft_ttl`](Self::set_soft_ttl)), this returns `None` once the entry's
+  /// soft TTL passes, even though it's still present for [`get_stale`](Self::get_stale).
   ///
   /// # Example
   ///
@@ -95,7 +261,7 @@ where
   /// assert_eq!(value, Some(&"my_value"));
   /// # }
   /// ```
-  /// 
+  ///
   /// Without TTL:
   /// ```
   /// # #[cfg(not(feature = "ttl"))] {
@@ -106,28 +272,131 @@ where
   /// assert_eq!(cache.get(&"my_key"), Some(&"my_value"));
   /// # }
   /// ```
-  pub fn get<Q>(&self, key: &Q) -> Option<&V> 
-  where 
+  pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + std::hash::Hash + Eq,
+  {
+    let policy = self.policy;
+
+    #[cfg(feature = "ttl")] {
+      let now = Instant::now();
+      let entry = self.map.get_mut(key).filter(|entry| {
+        // Never outlive the hard expiry, even if a soft TTL longer than it was configured.
+        entry.expires_at > now && entry.soft_expires_at.unwrap_or(entry.expires_at) > now
+      })?;
+      if policy == Policy::S3Fifo {
+        entry.freq = (entry.freq + 1).min(3);
+      }
+      Some(&entry.value)
+    }
+
+    #[cfg(not(feature = "ttl"))] {
+      let entry = self.map.get_mut(key)?;
+      if policy == Policy::S3Fifo {
+        entry.freq = (entry.freq + 1).min(3);
+      }
+      Some(&entry.value)
+    }
+  }
+
+  #[cfg(feature = "ttl")]
+  /// Retrieves a value even if it's past its soft TTL, as long as it hasn't passed its hard
+  /// TTL (`default_ttl`/the per-entry TTL), for stale-while-revalidate reads.
+  ///
+  /// Returns `Some((&V, stale))` where `stale` is `true` once the entry is past its soft TTL
+  /// (see [`set_soft_ttl`](Self::set_soft_ttl)) — callers typically use that flag to decide
+  /// whether to kick off a background refresh while still serving the stale value. `stale` is
+  /// always `false` if no soft TTL is configured, or if this particular entry has none (e.g. it
+  /// was inserted via [`insert_with_ttl`](Self::insert_with_ttl)).
+  ///
+  /// This does not bump the S3-FIFO frequency counter the way [`get`](Self::get) does, since a
+  /// stale read isn't necessarily a sign the entry is still wanted.
+  pub fn get_stale<Q>(&self, key: &Q) -> Option<(&V, bool)>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + std::hash::Hash + Eq,
+  {
+    let now = Instant::now();
+    let entry = self.map.get(key).filter(|entry| entry.expires_at > now)?;
+    let stale = entry.soft_expires_at.is_some_and(|soft_expires_at| soft_expires_at <= now);
+    Some((&entry.value, stale))
+  }
+
+  /// Returns the existing unexpired value for `key` if present, otherwise computes it with `f`,
+  /// inserts it, and returns a reference to it.
+  ///
+  /// This avoids the double-lookup-then-insert dance (and the clone-heavy workarounds) of
+  /// checking [`get`](Self::get) and then calling [`insert`](Self::insert) by hand, and `f` is
+  /// only called on a miss. TTL semantics for the inserted value are the same as
+  /// [`insert`](Self::insert) (i.e. `default_ttl`/the configured soft TTL).
+  ///
+  /// Returns `None` if the entry `f` computed doesn't survive its own insertion — e.g. a
+  /// [weigher](Self::set_weigher) whose `max_weight` is smaller than the new value's weight, or
+  /// `max_size() == 0`, can evict the fresh entry immediately inside `insert`.
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The key to look up, and to insert under on a miss
+  /// * `f` - Computes the value to insert on a miss; not called on a hit
+  pub fn get_or_insert_with<F>(&mut self, key: K, f: F) -> Option<&V>
+  where
+    F: FnOnce() -> V,
+  {
+    if self.contains_live(&key) {
+      // Fetch directly from `map` rather than through `get`: `contains_live` already confirmed
+      // liveness with its own `Instant::now()`, and re-running `get`'s TTL filter here would be
+      // a TOCTOU — the entry could look expired by the time `get` takes its own snapshot, even
+      // though nothing has changed since `contains_live` ran moments ago.
+      let policy = self.policy;
+      let entry = self.map.get_mut(&key).expect("contains_live confirmed this key is present");
+      if policy == Policy::S3Fifo {
+        entry.freq = (entry.freq + 1).min(3);
+      }
+      return Some(&entry.value);
+    }
+    let value = f();
+    self.insert(key.clone(), value);
+    // The entry we just inserted above may already be gone: capacity/weight pruning inside
+    // `insert` can evict it right back out, so this is `None`, not a guaranteed hit.
+    self.map.get(&key).map(|entry| &entry.value)
+  }
+
+  /// Lazy (`Into`-converting) variant of [`get_or_insert_with`](Self::get_or_insert_with). See
+  /// [`insert_lazy`](Self::insert_lazy) for why you'll need to specify the cache types explicitly.
+  pub fn get_or_insert_with_lazy<Kinto, Vinto, F>(&mut self, key: Kinto, f: F) -> Option<&V>
+  where
+    Kinto: Into<K>,
+    Vinto: Into<V>,
+    F: FnOnce() -> Vinto,
+  {
+    self.get_or_insert_with(key.into(), || f().into())
+  }
+
+  // Like `get`, but doesn't bump the S3-FIFO frequency counter or return a reference, since
+  // it's only used to decide whether `get_or_insert_with` needs to call its closure.
+  fn contains_live<Q>(&self, key: &Q) -> bool
+  where
     K: Borrow<Q>,
     Q: ?Sized + std::hash::Hash + Eq,
   {
     #[cfg(feature = "ttl")] {
       let now = Instant::now();
-      self.map
-        .get(key)
-        .filter(|entry| entry.expires_at > now)
-        .map(|entry| &entry.value)
+      self.map.get(key).is_some_and(|entry| {
+        entry.expires_at > now && entry.soft_expires_at.unwrap_or(entry.expires_at) > now
+      })
     }
 
     #[cfg(not(feature = "ttl"))] {
-      self.map.get(key).map(|entry| &entry.value)
+      self.map.contains_key(key)
     }
   }
 
   /// Inserts a key-value pair into the cache.
   ///
   /// If the key already exists, its value is updated and TTL is refreshed.
-  /// If the cache is at capacity, the oldest entry is evicted.
+  /// If the cache is at capacity, the oldest entry is evicted (which entry counts as "oldest"
+  /// depends on the cache's [`Policy`]).
   ///
   /// # Arguments
   ///
@@ -135,52 +404,102 @@ where
   /// * `value` - The value to associate with the key
   pub fn insert(&mut self, key: K, value: V) {
     #[cfg(feature = "ttl")] {
-      let expires_at = Instant::now() + self.default_ttl;
-      
-      match self.map.entry(key.clone()) {
-        hash_map::Entry::Occupied(mut entry) => {
-          // Entry exists, just update it
-          entry.insert(CacheEntry { value, expires_at });
-        }
-        hash_map::Entry::Vacant(entry) => {
-          // Entry doesn't exist, insert it then prune
-          entry.insert(CacheEntry { value, expires_at });
-          self.order.push_back(key);
-          self.prune();
-        }
-      }
+      let now = Instant::now();
+      let expires_at = now + self.default_ttl;
+      let soft_expires_at = self.soft_ttl.map(|soft_ttl| now + soft_ttl);
+      self.insert_entry(key, CacheEntry { value, expires_at, soft_expires_at, freq: 0 });
     }
 
     #[cfg(not(feature = "ttl"))] {
-      match self.map.entry(key.clone()) {
-        hash_map::Entry::Occupied(mut entry) => {
-          entry.insert(CacheEntry { value });
+      self.insert_entry(key, CacheEntry { value, freq: 0 });
+    }
+  }
+
+  #[cfg(feature = "ttl")]
+  /// Inserts a key-value pair with a TTL that overrides [`default_ttl`](Self::default_ttl) for
+  /// this entry only.
+  ///
+  /// This lets callers mix short-lived and long-lived data in the same cache (e.g. auth tokens
+  /// vs. config), without having to flip `default_ttl` back and forth around each insert.
+  ///
+  /// Note that this entry won't have a soft TTL even if [`set_soft_ttl`](Self::set_soft_ttl) was
+  /// called, so [`get_stale`](Self::get_stale) will never report it as stale, only gone.
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The key to insert
+  /// * `value` - The value to associate with the key
+  /// * `ttl` - The time-to-live for this entry, overriding `default_ttl`
+  pub fn insert_with_ttl(&mut self, key: K, value: V, ttl: Duration) {
+    let expires_at = Instant::now() + ttl;
+    self.insert_entry(key, CacheEntry { value, expires_at, soft_expires_at: None, freq: 0 });
+  }
+
+  // Shared insertion path for both policies: updates in place if the key already exists,
+  // otherwise places the new entry according to `self.policy` and prunes.
+  fn insert_entry(&mut self, key: K, new_entry: CacheEntry<V>) {
+    let new_weight = self.weigher.as_ref().map(|weigher| weigher(&key, &new_entry.value));
+
+    match self.map.entry(key.clone()) {
+      hash_map::Entry::Occupied(mut entry) => {
+        if let Some(new_weight) = new_weight {
+          let old_weight = self.weigher.as_ref().unwrap()(entry.key(), &entry.get().value);
+          self.current_weight = self.current_weight.saturating_sub(old_weight) + new_weight;
+        }
+        entry.insert(new_entry);
+        // The entry count is unchanged, but a heavier value can push the weight over the bound.
+        self.prune_weight();
+      }
+      hash_map::Entry::Vacant(entry) => {
+        if let Some(new_weight) = new_weight {
+          self.current_weight += new_weight;
         }
-        hash_map::Entry::Vacant(entry) => {
-          entry.insert(CacheEntry { value });
-          self.order.push_back(key);
-          self.prune();
+        entry.insert(new_entry);
+        match self.policy {
+          Policy::Fifo => self.order.push_back(key),
+          Policy::S3Fifo => {
+            if let Some(pos) = self.ghost.iter().position(|k| k == &key) {
+              self.ghost.remove(pos);
+              self.main.push_back(key);
+            } else {
+              self.small.push_back(key);
+            }
+          }
         }
+        self.prune();
       }
     }
   }
 
+  // Removes `key` from `map` (if present), updates `current_weight` if a weigher is configured,
+  // and fires `on_evict` with the given reason. Does not touch `order`/`small`/`main`/`ghost`.
+  fn evict_map_entry(&mut self, key: &K, reason: EvictReason) {
+    let Some(entry) = self.map.remove(key) else { return };
+    if let Some(weigher) = &self.weigher {
+      let weight = weigher(key, &entry.value);
+      self.current_weight = self.current_weight.saturating_sub(weight);
+    }
+    if let Some(on_evict) = &mut self.on_evict {
+      on_evict(key.clone(), entry.value, reason);
+    }
+  }
+
   /// Inserts a key-value pair into the cache using types that can be converted into the key and value types.
   ///
   /// This is a convenience wrapper around [`insert`](Self::insert) that accepts any types implementing
-  /// `Into<K>` and `Into<V>`. Note that using only `insert_lazy` prevents type inference, so you'll 
+  /// `Into<K>` and `Into<V>`. Note that using only `insert_lazy` prevents type inference, so you'll
   /// need to explicitly specify the cache types:
   ///
   /// ```
   /// # #[cfg(feature = "ttl")] {
   /// use fifo_cache::FifoCache;
   /// use std::time::Duration;
-  /// 
+  ///
   /// // With insert - types are inferred
   /// let mut cache = FifoCache::new(100, Duration::from_secs(60));
   /// cache.insert("key", "value");  // FifoCache<&str, &str>
   ///
-  /// // With insert_lazy - types must be specified  
+  /// // With insert_lazy - types must be specified
   /// let mut cache: FifoCache<String, String> = FifoCache::new(100, Duration::from_secs(60));
   /// cache.insert_lazy("key", "value");  // &str -> String conversion
   /// # }
@@ -189,6 +508,13 @@ where
     self.insert(key.into(), value.into())
   }
 
+  #[cfg(feature = "ttl")]
+  /// Lazy (`Into`-converting) variant of [`insert_with_ttl`](Self::insert_with_ttl). See
+  /// [`insert_lazy`](Self::insert_lazy) for why you'll need to specify the cache types explicitly.
+  pub fn insert_lazy_with_ttl<Kinto: Into<K>, Vinto: Into<V>>(&mut self, key: Kinto, value: Vinto, ttl: Duration) {
+    self.insert_with_ttl(key.into(), value.into(), ttl)
+  }
+
   /// Removes a key from the cache.
   ///
   /// # Arguments
@@ -198,13 +524,28 @@ where
   /// # Returns
   ///
   /// `Some(V)` if the key existed, `None` otherwise.
-  pub fn remove<Q>(&mut self, key: &Q) -> Option<V> 
+  pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
   where
     K: Borrow<Q>,
     Q: ?Sized + std::hash::Hash + Eq,
   {
-    if let Some(entry) = self.map.remove(key) {
-      self.order.retain(|k| k.borrow() != key);
+    if let Some((k, entry)) = self.map.remove_entry(key) {
+      if let Some(weigher) = &self.weigher {
+        let weight = weigher(&k, &entry.value);
+        self.current_weight = self.current_weight.saturating_sub(weight);
+      }
+      match self.policy {
+        Policy::Fifo => {
+          self.order.retain(|k| k.borrow() != key);
+        }
+        Policy::S3Fifo => {
+          self.small.retain(|k| k.borrow() != key);
+          self.main.retain(|k| k.borrow() != key);
+        }
+      }
+      if let Some(on_evict) = &mut self.on_evict {
+        on_evict(k, entry.value.clone(), EvictReason::Removed);
+      }
       Some(entry.value)
     } else {
       None
@@ -225,24 +566,50 @@ where
   /// Removes all expired entries from the cache.
   pub fn cleanup_expired(&mut self) {
     let now = Instant::now();
-    self.order.retain(|key| {
-      if let Some(entry) = self.map.get(key) {
-        if entry.expires_at <= now {
-          self.map.remove(key);
-          false
+    let map = &mut self.map;
+    let weigher = &self.weigher;
+    let on_evict = &mut self.on_evict;
+    let mut current_weight = self.current_weight;
+    let mut retain_unexpired = |queue: &mut VecDeque<K>| {
+      queue.retain(|key| {
+        if let Some(entry) = map.get(key) {
+          if entry.expires_at <= now {
+            let removed = map.remove(key).unwrap();
+            if let Some(weigher) = weigher {
+              current_weight = current_weight.saturating_sub(weigher(key, &removed.value));
+            }
+            if let Some(on_evict) = on_evict {
+              on_evict(key.clone(), removed.value, EvictReason::Expired);
+            }
+            false
+          } else {
+            true
+          }
         } else {
-          true
+          false
         }
-      } else {
-        false
+      });
+    };
+
+    match self.policy {
+      Policy::Fifo => retain_unexpired(&mut self.order),
+      Policy::S3Fifo => {
+        retain_unexpired(&mut self.small);
+        retain_unexpired(&mut self.main);
       }
-    });
+    }
+
+    self.current_weight = current_weight;
   }
 
   /// Clears all entries from the cache.
   pub fn clear(&mut self) {
     self.map.clear();
     self.order.clear();
+    self.small.clear();
+    self.main.clear();
+    self.ghost.clear();
+    self.current_weight = 0;
   }
 
   /// Returns the maximum capacity of the cache.
@@ -251,7 +618,7 @@ where
   }
 
   /// Sets the maximum capacity of the cache.
-  /// 
+  ///
   /// # Arguments
   ///
   /// * `max_size` - The new maximum number of entries the cache can hold
@@ -264,6 +631,60 @@ where
     }
   }
 
+  /// Returns the eviction policy this cache was created with.
+  pub fn policy(&self) -> Policy {
+    self.policy
+  }
+
+  /// Enables weight-based capacity bounding: `weigher` computes a "weight" for each entry, and
+  /// the cache will evict oldest entries until `current_weight() <= max_weight`, in addition to
+  /// (not instead of) the existing entry-count cap from `max_size`.
+  ///
+  /// This is useful for memory-sensitive workloads caching payloads of wildly varying sizes
+  /// (e.g. `String`/`Vec<u8>` blobs), where bounding by entry count alone can't bound memory use.
+  ///
+  /// Calling this recomputes `current_weight` from the entries already in the cache and prunes
+  /// immediately if they're over `max_weight`.
+  ///
+  /// # Arguments
+  ///
+  /// * `weigher` - Computes the weight of a key-value pair
+  /// * `max_weight` - The maximum total weight the cache may hold
+  pub fn set_weigher<F>(&mut self, weigher: F, max_weight: usize)
+  where
+    F: Fn(&K, &V) -> usize + 'static,
+  {
+    self.current_weight = self.map.iter().map(|(k, entry)| weigher(k, &entry.value)).sum();
+    self.weigher = Some(Box::new(weigher));
+    self.max_weight = Some(max_weight);
+    self.prune();
+  }
+
+  /// Returns the total weight of all entries currently in the cache, as computed by the
+  /// weigher set via [`set_weigher`](Self::set_weigher). Always `0` if no weigher is set.
+  pub fn current_weight(&self) -> usize {
+    self.current_weight
+  }
+
+  /// Returns the maximum total weight the cache may hold, if weight-based bounding was enabled
+  /// via [`set_weigher`](Self::set_weigher).
+  pub fn max_weight(&self) -> Option<usize> {
+    self.max_weight
+  }
+
+  /// Registers a callback that fires whenever an entry leaves the cache, whether via capacity
+  /// pruning, [`cleanup_expired`](Self::cleanup_expired), or [`remove`](Self::remove) — see
+  /// [`EvictReason`]. Useful for flushing dirty data, closing resources, or updating metrics.
+  ///
+  /// Only one callback can be registered at a time; calling this again replaces the previous one.
+  /// [`clear`](Self::clear) drops entries without calling it.
+  pub fn set_on_evict<F>(&mut self, on_evict: F)
+  where
+    F: FnMut(K, V, EvictReason) + 'static,
+  {
+    self.on_evict = Some(Box::new(on_evict));
+  }
+
   #[cfg(feature = "ttl")]
   /// Returns the default TTL for cache entries.
   pub fn default_ttl(&self) -> Duration {
@@ -274,7 +695,7 @@ where
   /// Sets the default TTL for cache entries.
   /// Note that this will only affect entries that get inserted or updated after the change.
   /// Existing entries will keep their TTL until they expire.
-  /// 
+  ///
   /// # Arguments
   ///
   /// * `default_ttl` - The new default time-to-live for cache entries
@@ -282,14 +703,116 @@ where
     self.default_ttl = default_ttl;
   }
 
-  // Evicts oldest entries if at capacity
+  #[cfg(feature = "ttl")]
+  /// Returns the soft TTL for cache entries, if stale-while-revalidate reads are enabled via
+  /// [`set_soft_ttl`](Self::set_soft_ttl).
+  pub fn soft_ttl(&self) -> Option<Duration> {
+    self.soft_ttl
+  }
+
+  #[cfg(feature = "ttl")]
+  /// Sets the soft TTL for cache entries, enabling stale-while-revalidate reads: once an entry
+  /// is past its soft TTL, [`get`](Self::get) stops returning it but
+  /// [`get_stale`](Self::get_stale) keeps serving it (flagged as stale) until it passes its
+  /// hard TTL (`default_ttl`/the per-entry TTL).
+  ///
+  /// Like [`set_default_ttl`](Self::set_default_ttl), this only affects entries inserted or
+  /// updated after the change; existing entries keep whatever soft TTL (or lack thereof) they
+  /// were inserted with.
+  ///
+  /// # Arguments
+  ///
+  /// * `soft_ttl` - The new soft time-to-live for cache entries. A `soft_ttl` at or past the
+  ///   hard TTL (`default_ttl`/the per-entry TTL) has no effect: `get` never outlives the hard
+  ///   expiry regardless of how long the soft TTL is
+  pub fn set_soft_ttl(&mut self, soft_ttl: Duration) {
+    self.soft_ttl = Some(soft_ttl);
+  }
+
+  #[cfg(feature = "ttl")]
+  /// Sets the hard and soft TTL together in one call; equivalent to calling
+  /// [`set_default_ttl`](Self::set_default_ttl) and [`set_soft_ttl`](Self::set_soft_ttl) back to
+  /// back.
+  ///
+  /// # Arguments
+  ///
+  /// * `default_ttl` - The new default (hard) time-to-live for cache entries
+  /// * `soft_ttl` - The new soft time-to-live for cache entries, see
+  ///   [`set_soft_ttl`](Self::set_soft_ttl)
+  pub fn set_ttls(&mut self, default_ttl: Duration, soft_ttl: Duration) {
+    self.set_default_ttl(default_ttl);
+    self.set_soft_ttl(soft_ttl);
+  }
+
+  // Evicts oldest entries while over the entry-count or weight capacity, dispatching to
+  // the strategy for `self.policy`.
   fn prune(&mut self) {
+    match self.policy {
+      Policy::Fifo => self.prune_fifo(),
+      Policy::S3Fifo => self.prune_s3fifo(),
+    }
+    self.prune_weight();
+  }
+
+  fn prune_fifo(&mut self) {
     while self.order.len() > self.max_size {
       if let Some(old_key) = self.order.pop_front() {
-        self.map.remove(&old_key);
+        self.evict_map_entry(&old_key, EvictReason::Capacity);
+      }
+    }
+  }
+
+  // S3-FIFO eviction: `small` (~10% of `max_size`) is the probationary queue new keys land
+  // in, `main` (~90%) is where entries that proved themselves graduate to, and `ghost`
+  // remembers the keys (not values) of recently evicted entries so that a re-insert can skip
+  // straight to `main`.
+  fn prune_s3fifo(&mut self) {
+    let small_cap = (self.max_size / 10).max(1);
+    let main_cap = self.max_size.saturating_sub(small_cap);
+    let ghost_cap = main_cap;
+
+    while self.main.len() > main_cap {
+      let key = self.main.pop_front().unwrap();
+      let Some(entry) = self.map.get_mut(&key) else { continue };
+      if entry.freq > 0 {
+        entry.freq -= 1;
+        self.main.push_back(key);
+      } else {
+        self.evict_map_entry(&key, EvictReason::Capacity);
+      }
+    }
+
+    while self.small.len() > small_cap {
+      let key = self.small.pop_front().unwrap();
+      let Some(entry) = self.map.get_mut(&key) else { continue };
+      if entry.freq > 1 {
+        entry.freq = 0;
+        self.main.push_back(key);
+      } else {
+        self.evict_map_entry(&key, EvictReason::Capacity);
+        self.ghost.push_back(key);
+        if self.ghost.len() > ghost_cap {
+          self.ghost.pop_front();
+        }
       }
     }
   }
+
+  // Evicts oldest entries (regardless of frequency) until `current_weight` is back within
+  // `max_weight`, if a weigher is configured. This is in addition to, not instead of, the
+  // entry-count based eviction above.
+  fn prune_weight(&mut self) {
+    let Some(max_weight) = self.max_weight else { return };
+
+    while self.current_weight > max_weight {
+      let oldest = match self.policy {
+        Policy::Fifo => self.order.pop_front(),
+        Policy::S3Fifo => self.small.pop_front().or_else(|| self.main.pop_front()),
+      };
+      let Some(key) = oldest else { break };
+      self.evict_map_entry(&key, EvictReason::Capacity);
+    }
+  }
 }
 
 impl<K, V> Default for FifoCache<K, V>
@@ -1,5 +1,5 @@
 
-use fifo_cache::FifoCache;
+use fifo_cache::{EvictReason, FifoCache, Policy};
 #[cfg(feature = "ttl")]
 use std::thread;
 #[cfg(feature = "ttl")]
@@ -109,6 +109,34 @@ fn test_ttl_expiration() {
   assert_eq!(cache.get(&"key"), None);
 }
 
+#[cfg(feature = "ttl")]
+#[test]
+fn test_insert_with_ttl_overrides_default() {
+  let mut cache = FifoCache::new(10, Duration::from_millis(150));
+
+  cache.insert("short", "value1");
+  cache.insert_with_ttl("long", "value2", Duration::from_secs(60));
+
+  thread::sleep(Duration::from_millis(200));
+
+  // "short" used the default TTL and should have expired by now.
+  assert_eq!(cache.get(&"short"), None);
+  // "long" overrode it with a much longer TTL and should still be there.
+  assert_eq!(cache.get(&"long"), Some(&"value2"));
+}
+
+#[cfg(feature = "ttl")]
+#[test]
+fn test_insert_lazy_with_ttl() {
+  let mut cache: FifoCache<String, String> = FifoCache::new(10, Duration::from_secs(60));
+
+  cache.insert_lazy_with_ttl("key", "value", Duration::from_millis(100));
+  assert_eq!(cache.get("key"), Some(&String::from("value")));
+
+  thread::sleep(Duration::from_millis(150));
+  assert_eq!(cache.get("key"), None);
+}
+
 #[test]
 fn test_update_existing() {
   let mut cache = FifoCache::new(
@@ -166,3 +194,302 @@ fn test_lazy() {
   assert_eq!(cache.remove("key2"), Some(String::from("value2")));
   assert_eq!(cache.get("key2"), None);
 }
+
+#[test]
+fn test_s3fifo_keeps_frequently_accessed_entries() {
+  let mut cache = FifoCache::with_policy(
+    100,
+    #[cfg(feature = "ttl")]
+    Duration::from_secs(60),
+    Policy::S3Fifo,
+  );
+  assert_eq!(cache.policy(), Policy::S3Fifo);
+
+  // Fill the small (probationary) queue, which is sized ~10% of max_size.
+  for i in 0..10 {
+    cache.insert(i, i);
+  }
+  // Keep re-requesting "0" so it earns enough frequency to be promoted to the main queue
+  // once it cycles out of the small queue, instead of being dropped.
+  for _ in 0..3 {
+    assert_eq!(cache.get(&0), Some(&0));
+  }
+
+  // Insert enough new keys to push every other initial entry out of the small queue.
+  for i in 10..20 {
+    cache.insert(i, i);
+  }
+
+  assert_eq!(cache.get(&0), Some(&0));
+  assert_eq!(cache.get(&1), None);
+}
+
+#[test]
+fn test_s3fifo_ghost_readmission_skips_to_main() {
+  let mut cache = FifoCache::with_policy(
+    10,
+    #[cfg(feature = "ttl")]
+    Duration::from_secs(60),
+    Policy::S3Fifo,
+  );
+
+  cache.insert("a", 1);
+  // "a" is the only entry in the small queue (~10% of max_size == 1), so the very next
+  // insert evicts it straight to the ghost queue since it was never re-requested.
+  cache.insert("b", 2);
+  assert_eq!(cache.get(&"a"), None);
+
+  // Re-inserting "a" should find it in the ghost queue and admit it directly into the main queue.
+  cache.insert("a", 3);
+  assert_eq!(cache.get(&"a"), Some(&3));
+}
+
+#[test]
+fn test_weigher_bounds_by_weight_not_just_count() {
+  let mut cache: FifoCache<&str, String> = FifoCache::new(
+    100,
+    #[cfg(feature = "ttl")]
+    Duration::from_secs(60),
+  );
+  cache.set_weigher(|_k, v: &String| v.len(), 10);
+
+  cache.insert("a", "12345".to_string()); // weight 5, total 5
+  cache.insert("b", "1234".to_string()); // weight 4, total 9
+  assert_eq!(cache.current_weight(), 9);
+  assert_eq!(cache.max_weight(), Some(10));
+
+  cache.insert("c", "123".to_string()); // weight 3, total would be 12 > 10, evicts "a"
+  assert_eq!(cache.get(&"a"), None);
+  assert_eq!(cache.get(&"b"), Some(&"1234".to_string()));
+  assert_eq!(cache.get(&"c"), Some(&"123".to_string()));
+  assert_eq!(cache.current_weight(), 7);
+}
+
+#[test]
+fn test_weigher_update_replaces_old_weight() {
+  let mut cache: FifoCache<&str, String> = FifoCache::new(
+    100,
+    #[cfg(feature = "ttl")]
+    Duration::from_secs(60),
+  );
+  cache.set_weigher(|_k, v: &String| v.len(), 100);
+
+  cache.insert("a", "12345".to_string()); // weight 5
+  assert_eq!(cache.current_weight(), 5);
+
+  cache.insert("a", "1".to_string()); // weight 1, replacing the old weight of 5
+  assert_eq!(cache.current_weight(), 1);
+
+  assert_eq!(cache.remove(&"a"), Some("1".to_string()));
+  assert_eq!(cache.current_weight(), 0);
+}
+
+#[test]
+fn test_with_weigher_bounds_from_construction() {
+  let mut cache: FifoCache<&str, String> = FifoCache::with_weigher(
+    100,
+    #[cfg(feature = "ttl")]
+    Duration::from_secs(60),
+    Policy::Fifo,
+    |_k, v: &String| v.len(),
+    10,
+  );
+
+  cache.insert("a", "12345".to_string()); // weight 5, total 5
+  cache.insert("b", "1234".to_string()); // weight 4, total 9
+  assert_eq!(cache.current_weight(), 9);
+  assert_eq!(cache.max_weight(), Some(10));
+
+  cache.insert("c", "123".to_string()); // weight 3, total would be 12 > 10, evicts "a"
+  assert_eq!(cache.get(&"a"), None);
+  assert_eq!(cache.get(&"b"), Some(&"1234".to_string()));
+  assert_eq!(cache.get(&"c"), Some(&"123".to_string()));
+}
+
+#[test]
+fn test_on_evict_fires_for_capacity_and_removal() {
+  let mut cache = FifoCache::new(
+    2,
+    #[cfg(feature = "ttl")]
+    Duration::from_secs(60),
+  );
+
+  let evicted = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+  let evicted_clone = evicted.clone();
+  cache.set_on_evict(move |k, v, reason| evicted_clone.borrow_mut().push((k, v, reason)));
+
+  cache.insert("a", 1);
+  cache.insert("b", 2);
+  cache.insert("c", 3); // Should evict "a" due to capacity
+
+  assert_eq!(*evicted.borrow(), vec![("a", 1, EvictReason::Capacity)]);
+
+  cache.remove(&"b");
+  assert_eq!(
+    *evicted.borrow(),
+    vec![("a", 1, EvictReason::Capacity), ("b", 2, EvictReason::Removed)]
+  );
+}
+
+#[cfg(feature = "ttl")]
+#[test]
+fn test_on_evict_fires_for_expiration() {
+  let mut cache = FifoCache::new(10, Duration::from_millis(100));
+
+  let evicted = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+  let evicted_clone = evicted.clone();
+  cache.set_on_evict(move |k, v, reason| evicted_clone.borrow_mut().push((k, v, reason)));
+
+  cache.insert("key", "value");
+  thread::sleep(Duration::from_millis(150));
+  cache.cleanup_expired();
+
+  assert_eq!(*evicted.borrow(), vec![("key", "value", EvictReason::Expired)]);
+}
+
+#[cfg(feature = "ttl")]
+#[test]
+fn test_get_stale_serves_value_past_soft_ttl() {
+  let mut cache = FifoCache::new(10, Duration::from_millis(400));
+  cache.set_soft_ttl(Duration::from_millis(100));
+
+  cache.insert("key", "value");
+  assert_eq!(cache.get(&"key"), Some(&"value"));
+  assert_eq!(cache.get_stale(&"key"), Some((&"value", false)));
+
+  thread::sleep(Duration::from_millis(150));
+
+  // Past the soft TTL: plain `get` stops returning it, but `get_stale` keeps serving it,
+  // flagged as stale.
+  assert_eq!(cache.get(&"key"), None);
+  assert_eq!(cache.get_stale(&"key"), Some((&"value", true)));
+
+  thread::sleep(Duration::from_millis(300));
+
+  // Past the hard TTL: gone for good.
+  assert_eq!(cache.get_stale(&"key"), None);
+}
+
+#[cfg(feature = "ttl")]
+#[test]
+fn test_get_never_outlives_hard_ttl_even_with_longer_soft_ttl() {
+  let mut cache = FifoCache::new(10, Duration::from_millis(50));
+  cache.set_soft_ttl(Duration::from_millis(500));
+
+  cache.insert("key", "value");
+  thread::sleep(Duration::from_millis(120));
+
+  // The soft TTL (500ms) hasn't passed yet, but the hard TTL (50ms) has: `get` must agree with
+  // `get_stale`/`cleanup_expired` that the entry is gone, not keep serving it until 500ms.
+  assert_eq!(cache.get(&"key"), None);
+  assert_eq!(cache.get_stale(&"key"), None);
+}
+
+#[cfg(feature = "ttl")]
+#[test]
+fn test_set_ttls_sets_both_hard_and_soft() {
+  let mut cache = FifoCache::new(10, Duration::from_secs(60));
+  cache.set_ttls(Duration::from_millis(400), Duration::from_millis(100));
+
+  cache.insert("key", "value");
+  assert_eq!(cache.default_ttl(), Duration::from_millis(400));
+  assert_eq!(cache.soft_ttl(), Some(Duration::from_millis(100)));
+
+  thread::sleep(Duration::from_millis(150));
+
+  // Past the soft TTL but not the hard TTL: same stale-while-revalidate behavior as setting
+  // both TTLs separately via `set_default_ttl`/`set_soft_ttl`.
+  assert_eq!(cache.get(&"key"), None);
+  assert_eq!(cache.get_stale(&"key"), Some((&"value", true)));
+}
+
+#[cfg(feature = "ttl")]
+#[test]
+fn test_insert_with_ttl_has_no_soft_expiry() {
+  let mut cache = FifoCache::new(10, Duration::from_secs(60));
+  cache.set_soft_ttl(Duration::from_millis(100));
+
+  cache.insert_with_ttl("key", "value", Duration::from_millis(150));
+  thread::sleep(Duration::from_millis(120));
+
+  // An entry inserted via `insert_with_ttl` has no soft expiry, so it's never reported stale.
+  assert_eq!(cache.get_stale(&"key"), Some((&"value", false)));
+}
+
+#[test]
+fn test_get_or_insert_with_computes_only_on_miss() {
+  let mut cache = FifoCache::new(
+    10,
+    #[cfg(feature = "ttl")]
+    Duration::from_secs(60),
+  );
+
+  let mut calls = 0;
+  assert_eq!(cache.get_or_insert_with("key", || { calls += 1; 42 }), Some(&42));
+  assert_eq!(calls, 1);
+
+  // Second call is a hit, so the closure must not run again.
+  assert_eq!(cache.get_or_insert_with("key", || { calls += 1; 0 }), Some(&42));
+  assert_eq!(calls, 1);
+}
+
+#[cfg(feature = "ttl")]
+#[test]
+fn test_get_or_insert_with_recomputes_after_expiry() {
+  let mut cache = FifoCache::new(10, Duration::from_millis(100));
+
+  cache.get_or_insert_with("key", || 1);
+  thread::sleep(Duration::from_millis(150));
+
+  assert_eq!(cache.get_or_insert_with("key", || 2), Some(&2));
+}
+
+#[cfg(feature = "ttl")]
+#[test]
+fn test_get_or_insert_with_zero_ttl_does_not_panic() {
+  // A zero `default_ttl` is a legal construction: the entry `get_or_insert_with` just inserted
+  // is already expired by the time it would look it up through `get`, so it must not round-trip
+  // through `get`'s expiry filter to fetch the value back.
+  let mut cache = FifoCache::new(10, Duration::from_secs(0));
+
+  assert_eq!(cache.get_or_insert_with("key", || 42), Some(&42));
+}
+
+#[test]
+fn test_get_or_insert_with_evicted_by_weigher_returns_none() {
+  // A weigher whose `max_weight` is smaller than the freshly computed value's weight evicts
+  // that very entry during the `insert` inside `get_or_insert_with` -- this must come back as
+  // `None`, not panic.
+  let mut cache: FifoCache<&str, String> = FifoCache::new(
+    10,
+    #[cfg(feature = "ttl")]
+    Duration::from_secs(60),
+  );
+  cache.set_weigher(|_k, v: &String| v.len(), 3);
+
+  assert_eq!(cache.get_or_insert_with("k", || "12345".to_string()), None);
+  assert_eq!(cache.get(&"k"), None);
+}
+
+#[test]
+fn test_get_or_insert_with_zero_max_size_returns_none() {
+  let mut cache: FifoCache<&str, i32> = FifoCache::new(
+    0,
+    #[cfg(feature = "ttl")]
+    Duration::from_secs(60),
+  );
+
+  assert_eq!(cache.get_or_insert_with("k", || 1), None);
+}
+
+#[test]
+fn test_get_or_insert_with_lazy() {
+  let mut cache: FifoCache<String, String> = FifoCache::new(
+    10,
+    #[cfg(feature = "ttl")]
+    Duration::from_secs(60),
+  );
+
+  assert_eq!(cache.get_or_insert_with_lazy("key", || "value"), Some(&String::from("value")));
+  assert_eq!(cache.get("key"), Some(&String::from("value")));
+}